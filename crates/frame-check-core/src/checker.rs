@@ -0,0 +1,155 @@
+//! Validates column references against the schemas [`crate::schema`]
+//! inferred for each tracked DataFrame variable.
+
+use ruff_python_ast::visitor::{Visitor, walk_expr, walk_stmt};
+use ruff_python_ast::{Expr, ExprStringLiteral, Stmt, StmtAssign};
+use ruff_text_size::Ranged;
+
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::levenshtein;
+use crate::resolver::Resolver;
+use crate::schema::Schemas;
+
+/// Checks the expressions owned directly by `stmt` against `schemas`.
+///
+/// This does *not* descend into nested blocks (`if`/`for`/`def`/...
+/// bodies): [`Context::analyze`](crate::context::Context::analyze) walks
+/// those itself, one statement at a time, so that a schema assigned by an
+/// earlier sibling statement is visible before a later one is checked.
+/// Recursing here too would check — and report — nested statements twice.
+pub fn check_stmt(stmt: &Stmt, schemas: &Schemas, resolver: &Resolver) -> Vec<Diagnostic> {
+    let mut checker = ColumnChecker {
+        schemas,
+        resolver,
+        diagnostics: Vec::new(),
+    };
+    checker.visit_stmt(stmt);
+    checker.diagnostics
+}
+
+struct ColumnChecker<'a> {
+    schemas: &'a Schemas,
+    resolver: &'a Resolver<'a>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> ColumnChecker<'a> {
+    fn check_column(&mut self, df: &str, literal: &ExprStringLiteral) {
+        let column = literal.value.to_str();
+        let Some(schema) = self.schemas.get(df) else {
+            return;
+        };
+        if schema.columns.iter().any(|c| c == column) {
+            return;
+        }
+        let message = match levenshtein::closest(column, &schema.columns) {
+            Some(suggestion) => {
+                format!("Unknown column \"{column}\" on `{df}`, did you mean \"{suggestion}\"?")
+            }
+            None => format!("Unknown column \"{column}\" on `{df}`"),
+        };
+        self.diagnostics
+            .push(Diagnostic::new(message, literal.range(), Severity::Warning));
+    }
+
+    fn check_call(&mut self, call: &Expr) {
+        let Some(call) = call.as_call_expr() else {
+            return;
+        };
+        let Some(attr) = call.func.as_attribute_expr() else {
+            return;
+        };
+        let Some(receiver) = attr.value.as_name_expr() else {
+            return;
+        };
+        if !self.schemas.contains_key(receiver.id.as_str()) {
+            return;
+        }
+        let df = receiver.id.to_string();
+        let method = attr.attr.as_str();
+        let library = self.resolver.library;
+
+        if library.column_arg_methods().contains(&method) {
+            let literals = call
+                .arguments
+                .args
+                .iter()
+                .chain(call.arguments.keywords.iter().map(|kw| &kw.value))
+                .filter_map(Expr::as_string_literal_expr);
+            for literal in literals {
+                self.check_column(&df, literal);
+            }
+        } else if library.column_expr_methods().contains(&method) {
+            for arg in call
+                .arguments
+                .args
+                .iter()
+                .chain(call.arguments.keywords.iter().map(|kw| &kw.value))
+            {
+                let mut finder = ColCallFinder {
+                    resolver: self.resolver,
+                    literals: Vec::new(),
+                };
+                finder.visit_expr(arg);
+                for literal in finder.literals {
+                    self.check_column(&df, literal);
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for ColumnChecker<'a> {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        // `df["c"] = ...`'s target is a store, not a read: visiting it like
+        // any other subscript would flag the column it's creating as
+        // unknown. Only the value being assigned is a read worth checking.
+        if let Stmt::Assign(StmtAssign { value, .. }) = stmt {
+            self.visit_expr(value);
+            return;
+        }
+        walk_stmt(self, stmt);
+    }
+
+    // Stop the walk at this statement's own expressions; see `check_stmt`.
+    fn visit_body(&mut self, _body: &'a [Stmt]) {}
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        if let Some(subscript) = expr.as_subscript_expr() {
+            if let Some(name) = subscript.value.as_name_expr() {
+                if let Some(literal) = subscript.slice.as_string_literal_expr() {
+                    self.check_column(name.id.as_str(), literal);
+                }
+            }
+        } else if expr.is_call_expr() {
+            self.check_call(expr);
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Collects the string argument of every `col("...")` call (however the
+/// library's `col` function was imported) reachable from an expression,
+/// e.g. inside `df.select(...)`.
+struct ColCallFinder<'a> {
+    resolver: &'a Resolver<'a>,
+    literals: Vec<&'a ExprStringLiteral>,
+}
+
+impl<'a> Visitor<'a> for ColCallFinder<'a> {
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        if let Some(call) = expr.as_call_expr() {
+            if self.resolver.is_col_call(&call.func) {
+                if let Some(literal) = call
+                    .arguments
+                    .args
+                    .first()
+                    .and_then(Expr::as_string_literal_expr)
+                {
+                    self.literals.push(literal);
+                }
+            }
+        }
+        walk_expr(self, expr);
+    }
+}
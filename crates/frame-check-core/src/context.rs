@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+
+use ruff_python_ast::visitor::{Visitor, walk_stmt};
+use ruff_python_ast::{Alias, ModModule, Stmt, StmtAssign};
+use ruff_python_parser::Parsed;
+use ruff_text_size::Ranged;
+
+use crate::checker;
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::library::Library;
+use crate::resolver::Resolver;
+use crate::schema::{self, Schemas};
+
+/// Tracks what we know about a single module's use of a DataFrame
+/// library while walking its statements.
+#[derive(Debug)]
+pub struct Context<'a> {
+    library: Library,
+    import: Option<&'a Alias>,
+    /// Names bound via `from <library> import ...`, e.g. `DataFrame` and
+    /// `col` for `from polars import DataFrame, col`.
+    direct_imports: HashSet<String>,
+    schemas: Schemas,
+}
+
+impl<'a> Context<'a> {
+    pub fn new(library: Library) -> Self {
+        Context {
+            library,
+            import: None,
+            direct_imports: HashSet::new(),
+            schemas: Schemas::new(),
+        }
+    }
+
+    pub fn get_import_as_name(&self) -> Option<String> {
+        self.import
+            .and_then(|alias| alias.asname.as_ref().map(|name| name.id().to_string()))
+    }
+
+    fn resolver(&self) -> Resolver<'_> {
+        let alias = self
+            .import
+            .and_then(|alias| alias.asname.as_ref())
+            .map_or(self.library.root_module(), |name| name.id());
+        Resolver {
+            library: self.library,
+            alias,
+            direct_imports: &self.direct_imports,
+        }
+    }
+
+    pub fn try_set_import(&mut self, stmt: &'a Stmt) {
+        match stmt {
+            Stmt::Import(import) => {
+                for alias in &import.names {
+                    if alias.name.id() == self.library.root_module() {
+                        self.import = Some(alias);
+                    }
+                }
+            }
+            Stmt::ImportFrom(import_from) => {
+                let is_our_module = import_from
+                    .module
+                    .as_ref()
+                    .is_some_and(|module| module.id() == self.library.root_module());
+                if is_our_module {
+                    for alias in &import_from.names {
+                        let bound_name = alias
+                            .asname
+                            .as_ref()
+                            .map_or_else(|| alias.name.id().to_string(), |name| name.id().to_string());
+                        self.direct_imports.insert(bound_name);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn schemas(&self) -> &Schemas {
+        &self.schemas
+    }
+
+    fn track_assignment(&mut self, assign: &'a StmtAssign) {
+        // `df["new"] = ...` isn't a call `infer_schema` can make sense of,
+        // but it's still a column addition to the DataFrame it targets.
+        for target in &assign.targets {
+            let Some(subscript) = target.as_subscript_expr() else {
+                continue;
+            };
+            let Some(name) = subscript.value.as_name_expr() else {
+                continue;
+            };
+            let Some(literal) = subscript.slice.as_string_literal_expr() else {
+                continue;
+            };
+            let Some(schema) = self.schemas.get_mut(name.id.as_str()) else {
+                continue;
+            };
+            let column = literal.value.to_str();
+            if !schema.columns.iter().any(|c| c == column) {
+                schema.columns.push(column.to_string());
+            }
+            schema.source = assign.range();
+        }
+
+        let resolver = self.resolver();
+        let Some(inferred) = schema::infer_schema(&assign.value, &resolver, &self.schemas) else {
+            return;
+        };
+        for target in &assign.targets {
+            if let Some(name) = target.as_name_expr() {
+                self.schemas.insert(name.id.to_string(), inferred.clone());
+            }
+        }
+    }
+
+    /// Walks `module`, updating tracked imports and schemas and collecting
+    /// diagnostics: real parser errors (malformed Python no longer breaks
+    /// analysis silently) plus column references that don't match the
+    /// schema inferred so far for their DataFrame.
+    ///
+    /// Walks into nested blocks (`if`/`for`/`while`/`with`/`def`/`class`/
+    /// `try` bodies) as well as the module's top level, so a DataFrame
+    /// assigned or queried inside a function or branch is tracked and
+    /// checked just like one at module scope. Nesting isn't treated as a
+    /// separate scope: a name assigned inside one `if` branch is still
+    /// visible afterwards, which is imprecise but matches how a simple,
+    /// flow-insensitive linter like this one is expected to behave.
+    pub fn analyze(&mut self, parsed: &'a Parsed<ModModule>) -> Vec<Diagnostic> {
+        // Surface parse errors first: a checker diagnostic downstream of a
+        // syntax error is likely to be noise the user doesn't need yet.
+        let mut diagnostics: Vec<Diagnostic> = parsed
+            .errors()
+            .iter()
+            .map(|err| Diagnostic::new(err.to_string(), err.location, Severity::Error))
+            .collect();
+
+        let mut walker = Walker {
+            ctx: self,
+            diagnostics: Vec::new(),
+        };
+        for stmt in parsed.syntax().body.iter() {
+            walker.visit_stmt(stmt);
+        }
+        diagnostics.extend(walker.diagnostics);
+
+        diagnostics
+    }
+}
+
+/// Recurses through every statement in a module, tracking imports and
+/// schemas and checking column references in document order so that a
+/// schema assigned by an earlier statement — at any nesting depth — is
+/// visible when a later statement is checked.
+struct Walker<'ctx, 'a> {
+    ctx: &'ctx mut Context<'a>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'ctx, 'a> Visitor<'a> for Walker<'ctx, 'a> {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        // Check before tracking, so a statement's reads see the schema from
+        // before it: `df = df.drop("a")` reads "a" while it still exists.
+        self.diagnostics
+            .extend(checker::check_stmt(stmt, &self.ctx.schemas, &self.ctx.resolver()));
+        match stmt {
+            Stmt::Import(_) | Stmt::ImportFrom(_) => self.ctx.try_set_import(stmt),
+            Stmt::Assign(assign) => self.ctx.track_assignment(assign),
+            _ => (),
+        }
+        walk_stmt(self, stmt);
+    }
+}
@@ -0,0 +1,171 @@
+//! A small query database that memoizes per-file analysis results.
+//!
+//! Inspired by `ruff_db`'s `source_text`/`parsed_module`/`line_index`
+//! queries: each file is interned behind a [`FileId`] and carries a
+//! [`Revision`] that is bumped whenever its text changes. Queries cache
+//! their output alongside the revision they were computed from, so a
+//! lookup only recomputes when the stored revision is stale.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+
+use dashmap::DashMap;
+use ruff_python_ast::ModModule;
+use ruff_python_parser::{Mode, Parsed, parse_unchecked};
+
+use crate::fs::FileSystem;
+use crate::line_index::LineIndex;
+
+/// Identifies a file tracked by the [`Db`], independent of its path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FileId(u32);
+
+/// Monotonically increasing version of a file's contents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Revision(u64);
+
+struct Memo<T> {
+    revision: Revision,
+    value: Arc<T>,
+}
+
+/// The analysis database: owns every tracked file's source text and the
+/// memo tables for queries derived from it.
+#[derive(Default)]
+pub struct Db {
+    next_file_id: AtomicU64,
+    sources: DashMap<FileId, (Revision, Arc<str>)>,
+    paths: DashMap<PathBuf, FileId>,
+    parsed_module_cache: DashMap<FileId, Memo<Parsed<ModModule>>>,
+    line_index_cache: DashMap<FileId, Memo<LineIndex>>,
+}
+
+impl Db {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a new file with the given initial contents and returns
+    /// the [`FileId`] used to refer to it in subsequent queries.
+    pub fn add_file(&self, text: impl Into<Arc<str>>) -> FileId {
+        let id = FileId(self.next_file_id.fetch_add(1, Ordering::Relaxed) as u32);
+        self.sources.insert(id, (Revision(0), text.into()));
+        id
+    }
+
+    /// Resolves `path` to a [`FileId`], reading it through `fs` and
+    /// interning it on first use. Subsequent calls for the same path
+    /// return the same `FileId` without re-reading.
+    pub fn file_for_path(&self, fs: &dyn FileSystem, path: &Path) -> io::Result<FileId> {
+        if let Some(id) = self.paths.get(path) {
+            return Ok(*id);
+        }
+        let text = fs.read_to_string(path)?;
+        let id = self.add_file(text);
+        self.paths.insert(path.to_path_buf(), id);
+        Ok(id)
+    }
+
+    /// Overwrites `file`'s contents and bumps its revision, invalidating
+    /// every downstream query the next time it's queried.
+    pub fn set_source_text(&self, file: FileId, text: impl Into<Arc<str>>) {
+        let mut entry = self.sources.get_mut(&file).expect("unknown file");
+        entry.0 = Revision(entry.0.0 + 1);
+        entry.1 = text.into();
+    }
+
+    /// The `source_text` query: the file's current contents.
+    pub fn source_text(&self, file: FileId) -> Arc<str> {
+        self.sources.get(&file).expect("unknown file").1.clone()
+    }
+
+    fn current_revision(&self, file: FileId) -> Revision {
+        self.sources.get(&file).expect("unknown file").0
+    }
+
+    /// The `parsed_module` query: the file parsed into an AST, reparsed
+    /// only when the file's revision has advanced since the last call.
+    ///
+    /// Uses `parse_unchecked` rather than the `Result`-returning
+    /// `parse_module`, which fails outright on any syntax error: an LSP
+    /// reparses on every keystroke, so mid-edit buffers are routinely
+    /// invalid and we still want the partial AST plus `errors()` instead
+    /// of nothing at all.
+    pub fn parsed_module(&self, file: FileId) -> Arc<Parsed<ModModule>> {
+        let revision = self.current_revision(file);
+        if let Some(memo) = self.parsed_module_cache.get(&file) {
+            if memo.revision == revision {
+                return memo.value.clone();
+            }
+        }
+        let text = self.source_text(file);
+        let parsed = Arc::new(
+            parse_unchecked(&text, Mode::Module)
+                .try_into_module()
+                .expect("parsing in Mode::Module always yields a ModModule"),
+        );
+        self.parsed_module_cache.insert(
+            file,
+            Memo {
+                revision,
+                value: parsed.clone(),
+            },
+        );
+        parsed
+    }
+
+    /// The `line_index` query: the offset-to-position table for the
+    /// file's current text, recomputed only when the revision advances.
+    pub fn line_index(&self, file: FileId) -> Arc<LineIndex> {
+        let revision = self.current_revision(file);
+        if let Some(memo) = self.line_index_cache.get(&file) {
+            if memo.revision == revision {
+                return memo.value.clone();
+            }
+        }
+        let text = self.source_text(file);
+        let index = Arc::new(LineIndex::from_source_text(&text));
+        self.line_index_cache.insert(
+            file,
+            Memo {
+                revision,
+                value: index.clone(),
+            },
+        );
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsed_module_is_cached_until_revision_changes() {
+        let db = Db::new();
+        let file = db.add_file("x = 1\n");
+
+        let first = db.parsed_module(file);
+        let second = db.parsed_module(file);
+        assert!(Arc::ptr_eq(&first, &second));
+
+        db.set_source_text(file, "x = 2\n");
+        let third = db.parsed_module(file);
+        assert!(!Arc::ptr_eq(&first, &third));
+    }
+
+    #[test]
+    fn line_index_tracks_source_text_revision() {
+        let db = Db::new();
+        let file = db.add_file("a\nb\n");
+        let first = db.line_index(file);
+
+        db.set_source_text(file, "a\nb\nc\n");
+        let second = db.line_index(file);
+        assert!(!Arc::ptr_eq(&first, &second));
+    }
+}
@@ -0,0 +1,27 @@
+//! Problems found while analyzing a file, independent of any particular
+//! consumer (LSP, CLI, ...).
+
+use ruff_text_size::TextRange;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub message: String,
+    pub range: TextRange,
+    pub severity: Severity,
+}
+
+impl Diagnostic {
+    pub fn new(message: impl Into<String>, range: TextRange, severity: Severity) -> Self {
+        Diagnostic {
+            message: message.into(),
+            range,
+            severity,
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! File-system abstraction so analysis can run over real files on disk or
+//! over an editor's unsaved buffers, without the rest of the crate caring
+//! which.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use dashmap::DashMap;
+
+pub trait FileSystem: Send + Sync {
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+}
+
+/// Reads files straight from disk. Used by the batch CLI.
+pub struct OsFileSystem;
+
+impl FileSystem for OsFileSystem {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+}
+
+/// Holds in-memory buffers, such as unsaved editor buffers, keyed by path
+/// (typically a `file://` URI's path component), for files whose on-disk
+/// contents may be stale or nonexistent. The LSP does not read through it:
+/// it registers open buffers with [`crate::db::Db::add_file`] directly.
+#[derive(Default)]
+pub struct MemoryFileSystem {
+    overlays: DashMap<PathBuf, String>,
+}
+
+impl MemoryFileSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_text(&self, path: impl Into<PathBuf>, text: impl Into<String>) {
+        self.overlays.insert(path.into(), text.into());
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.overlays
+            .get(path)
+            .map(|text| text.clone())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no buffer open for {}", path.display()),
+                )
+            })
+    }
+}
+
+pub fn is_python_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "py")
+}
+
+/// Recursively collects every `.py` file under `root`, for the CLI's
+/// batch mode.
+pub fn discover_python_files(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if is_python_file(&path) {
+                files.push(path);
+            }
+        }
+    }
+    Ok(files)
+}
@@ -0,0 +1,60 @@
+//! Small edit-distance helper used to suggest a likely-intended column
+//! name when a reference doesn't match any column in scope.
+
+/// The classic Wagner-Fischer edit distance between two strings.
+pub fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut prev_diag = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let new_value = (row[j] + 1).min(row[j - 1] + 1).min(prev_diag + cost);
+            prev_diag = row[j];
+            row[j] = new_value;
+        }
+    }
+    row[b.len()]
+}
+
+/// Picks the closest candidate to `target`, if any is within a plausible
+/// typo distance (at most half of the longer string's length, with a
+/// minimum of 2 so short names still get a suggestion).
+pub fn closest<'a>(target: &str, candidates: impl IntoIterator<Item = &'a String>) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (candidate.as_str(), distance(target, candidate)))
+        .filter(|(candidate, dist)| *dist <= (target.len().max(candidate.len()) / 2).max(2))
+        .min_by_key(|(_, dist)| *dist)
+        .map(|(candidate, _)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_strings_have_zero_distance() {
+        assert_eq!(distance("column", "column"), 0);
+    }
+
+    #[test]
+    fn single_typo() {
+        assert_eq!(distance("nmae", "name"), 2);
+    }
+
+    #[test]
+    fn closest_picks_nearest_candidate() {
+        let candidates = vec!["name".to_string(), "age".to_string(), "address".to_string()];
+        assert_eq!(closest("nmae", &candidates), Some("name"));
+    }
+
+    #[test]
+    fn closest_returns_none_when_too_different() {
+        let candidates = vec!["name".to_string()];
+        assert_eq!(closest("zzzzzzzz", &candidates), None);
+    }
+}
@@ -0,0 +1,25 @@
+//! Core analysis engine shared by the `frame-check` CLI and `frame-check-lsp`.
+
+pub mod checker;
+pub mod context;
+pub mod db;
+pub mod diagnostic;
+pub mod fs;
+pub mod levenshtein;
+pub mod library;
+pub mod line_index;
+pub mod resolver;
+pub mod schema;
+
+pub use context::Context;
+pub use diagnostic::Diagnostic;
+pub use library::Library;
+
+use db::{Db, FileId};
+
+/// Analyzes `file` through `db`: runs a [`Context`] configured for
+/// `library` over the file's memoized parsed module.
+pub fn analyze(db: &Db, library: Library, file: FileId) -> Vec<Diagnostic> {
+    let module = db.parsed_module(file);
+    Context::new(library).analyze(&module)
+}
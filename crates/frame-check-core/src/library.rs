@@ -0,0 +1,81 @@
+//! DataFrame library idioms that differ between polars and pandas: the
+//! constructor names, the transform methods that add/rename/drop columns,
+//! and whether the library has a `col()`-style column-expression builder.
+
+/// The DataFrame library a [`crate::Context`] is configured to recognize.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Library {
+    Polars,
+    Pandas,
+}
+
+impl Library {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "polars" => Some(Library::Polars),
+            "pandas" => Some(Library::Pandas),
+            _ => None,
+        }
+    }
+
+    /// The top-level module name, e.g. `import polars`.
+    pub fn root_module(&self) -> &'static str {
+        match self {
+            Library::Polars => "polars",
+            Library::Pandas => "pandas",
+        }
+    }
+
+    /// Calls that construct or load a DataFrame with a known schema.
+    pub fn constructors(&self) -> &'static [&'static str] {
+        match self {
+            Library::Polars => &["DataFrame", "read_csv"],
+            Library::Pandas => &["DataFrame", "read_csv", "read_excel"],
+        }
+    }
+
+    /// Chained calls that add columns to an existing DataFrame.
+    pub fn adding_transforms(&self) -> &'static [&'static str] {
+        match self {
+            Library::Polars => &["with_columns"],
+            Library::Pandas => &["assign"],
+        }
+    }
+
+    /// Chained calls that rename existing columns.
+    pub fn rename_transforms(&self) -> &'static [&'static str] {
+        &["rename"]
+    }
+
+    /// Chained calls that drop existing columns.
+    pub fn dropping_transforms(&self) -> &'static [&'static str] {
+        &["drop"]
+    }
+
+    /// Methods whose string/keyword arguments name columns directly,
+    /// e.g. `df.sort("a")`.
+    pub fn column_arg_methods(&self) -> &'static [&'static str] {
+        match self {
+            Library::Polars => &["group_by", "sort", "drop"],
+            Library::Pandas => &["groupby", "sort_values", "drop"],
+        }
+    }
+
+    /// Methods whose arguments are column-expressions built with
+    /// [`Library::col_function`], e.g. `df.select(pl.col("a"))`.
+    pub fn column_expr_methods(&self) -> &'static [&'static str] {
+        match self {
+            Library::Polars => &["select", "filter"],
+            Library::Pandas => &[],
+        }
+    }
+
+    /// The name of the column-expression constructor, if the library has
+    /// one (polars' `col`; pandas has no equivalent).
+    pub fn col_function(&self) -> Option<&'static str> {
+        match self {
+            Library::Polars => Some("col"),
+            Library::Pandas => None,
+        }
+    }
+}
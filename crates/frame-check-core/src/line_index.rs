@@ -0,0 +1,79 @@
+//! Maps byte offsets into a source file to line/column positions.
+
+use ruff_text_size::TextSize;
+
+/// A zero-indexed line and column position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LineColumn {
+    pub line: u32,
+    /// UTF-16 code unit offset within the line, as required by LSP.
+    pub column: u32,
+}
+
+/// Byte offsets of each line start in a source file, used to convert
+/// `TextSize` offsets from the AST into line/column positions.
+#[derive(Debug)]
+pub struct LineIndex {
+    /// Byte offset of the start of each line. Always begins with `0`.
+    line_starts: Vec<u32>,
+}
+
+impl LineIndex {
+    pub fn from_source_text(text: &str) -> Self {
+        let mut line_starts = vec![0u32];
+        for (i, byte) in text.bytes().enumerate() {
+            if byte == b'\n' {
+                line_starts.push(i as u32 + 1);
+            }
+        }
+        LineIndex { line_starts }
+    }
+
+    /// Converts a byte offset into the source into a zero-indexed line and
+    /// a UTF-16 column, suitable for an LSP `Position`.
+    pub fn line_column(&self, offset: TextSize, text: &str) -> LineColumn {
+        let offset: u32 = offset.into();
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        let line_start = self.line_starts[line];
+        let column = text
+            .get(line_start as usize..offset as usize)
+            .unwrap_or_default()
+            .encode_utf16()
+            .count() as u32;
+        LineColumn {
+            line: line as u32,
+            column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_line() {
+        let index = LineIndex::from_source_text("abc");
+        let pos = index.line_column(TextSize::from(2), "abc");
+        assert_eq!(pos, LineColumn { line: 0, column: 2 });
+    }
+
+    #[test]
+    fn multiple_lines() {
+        let text = "abc\ndef\nghi";
+        let index = LineIndex::from_source_text(text);
+        let pos = index.line_column(TextSize::from(5), text);
+        assert_eq!(pos, LineColumn { line: 1, column: 1 });
+    }
+
+    #[test]
+    fn offset_at_eof() {
+        let text = "abc\ndef";
+        let index = LineIndex::from_source_text(text);
+        let pos = index.line_column(TextSize::from(text.len() as u32), text);
+        assert_eq!(pos, LineColumn { line: 1, column: 3 });
+    }
+}
@@ -1,55 +1,63 @@
-use ruff_python_ast::{Alias, Stmt};
-use ruff_python_parser::parse_module;
-use std::{fs::File, io::Read};
-
-#[derive(Default, Debug)]
-struct Context<'a> {
-    library: &'a str,
-    import: Option<&'a Alias>,
-    assignements: Vec<&'a Stmt>,
+use std::path::PathBuf;
+
+use frame_check_core::{
+    Library,
+    db::Db,
+    fs::{OsFileSystem, discover_python_files},
+};
+
+struct Cli {
+    target: PathBuf,
+    library: Library,
 }
 
-impl<'a> Context<'a> {
-    fn new(library: &'a str) -> Self {
-        Context {
-            library,
-            import: None,
-            assignements: Vec::new(),
+/// Parses `[--library <polars|pandas>] [path]`, defaulting to polars and
+/// `test.py` for anything not given.
+fn parse_args() -> Cli {
+    let mut library = Library::Polars;
+    let mut target = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--library" {
+            let name = args
+                .next()
+                .unwrap_or_else(|| panic!("--library requires a value"));
+            library = Library::from_name(&name)
+                .unwrap_or_else(|| panic!("unknown --library \"{name}\" (expected polars or pandas)"));
+        } else {
+            target = Some(PathBuf::from(arg));
         }
     }
 
-    fn get_import_as_name(&self) -> Option<String> {
-        self.import
-            .and_then(|alias| alias.asname.as_ref().map(|name| name.id().to_string()))
-    }
-
-    fn try_set_import(&mut self, import: &'a Stmt) {
-        self.import = import
-            .as_import_stmt()
-            .and_then(|s| s.names.iter().find(|i| i.name.id() == self.library));
+    Cli {
+        target: target.unwrap_or_else(|| PathBuf::from("test.py")),
+        library,
     }
 }
 
 fn main() {
-    let mut ctx = Context::new("polars");
-
-    let mut buf = String::new();
-    File::open("test.py")
-        .expect("Failed to open file")
-        .read_to_string(&mut buf)
-        .expect("Failed to read file");
-
-    let module = parse_module(&buf).unwrap();
-    for stmt in module.syntax().body.iter() {
-        dbg!(&stmt);
-        match stmt {
-            Stmt::Import(_) => {
-                ctx.try_set_import(stmt);
-            }
-            Stmt::Assign(_) => (),
-            _ => (),
+    let Cli { target, library } = parse_args();
+
+    let fs = OsFileSystem;
+    let paths = if target.is_dir() {
+        discover_python_files(&target).expect("failed to walk directory")
+    } else {
+        vec![target]
+    };
+
+    let db = Db::new();
+    for path in paths {
+        let file = db
+            .file_for_path(&fs, &path)
+            .unwrap_or_else(|err| panic!("failed to read {}: {err}", path.display()));
+        let diagnostics = frame_check_core::analyze(&db, library, file);
+        if diagnostics.is_empty() {
+            continue;
+        }
+        println!("{}:", path.display());
+        for diagnostic in diagnostics {
+            println!("  {}", diagnostic.message);
         }
     }
-
-    dbg!(ctx.get_import_as_name());
 }
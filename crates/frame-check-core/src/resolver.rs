@@ -0,0 +1,65 @@
+//! Decides which names in a module actually refer to the configured
+//! DataFrame library, covering both `import polars as pl` (attribute
+//! access, `pl.DataFrame`) and `from polars import DataFrame` (bare
+//! names, `DataFrame`).
+
+use std::collections::HashSet;
+
+use ruff_python_ast::Expr;
+
+use crate::library::Library;
+
+pub struct Resolver<'a> {
+    pub library: Library,
+    /// The name `library` is accessed through, e.g. `pl` for
+    /// `import polars as pl`, or `polars` if it was imported bare.
+    pub alias: &'a str,
+    /// Names bound directly via `from <library> import ...`.
+    pub direct_imports: &'a HashSet<String>,
+}
+
+impl<'a> Resolver<'a> {
+    /// If `func` is a call to one of `library`'s constructors, either as
+    /// `<alias>.<Constructor>` or a bare `<Constructor>` that was
+    /// imported directly, returns the constructor's name.
+    pub fn constructor_name(&self, func: &Expr) -> Option<&'static str> {
+        if let Some(attr) = func.as_attribute_expr() {
+            if self.is_library_alias(&attr.value) {
+                return self.matching_constructor(attr.attr.as_str());
+            }
+            return None;
+        }
+        let name = func.as_name_expr()?;
+        if self.direct_imports.contains(name.id.as_str()) {
+            return self.matching_constructor(name.id.as_str());
+        }
+        None
+    }
+
+    /// Whether `func` is a call to the library's `col()`-style
+    /// column-expression builder, either as `<alias>.col` or a bare
+    /// `col` that was imported directly.
+    pub fn is_col_call(&self, func: &Expr) -> bool {
+        let Some(col_fn) = self.library.col_function() else {
+            return false;
+        };
+        if let Some(attr) = func.as_attribute_expr() {
+            return attr.attr.as_str() == col_fn && self.is_library_alias(&attr.value);
+        }
+        func.as_name_expr()
+            .is_some_and(|name| name.id.as_str() == col_fn && self.direct_imports.contains(col_fn))
+    }
+
+    fn is_library_alias(&self, expr: &Expr) -> bool {
+        expr.as_name_expr()
+            .is_some_and(|name| name.id.as_str() == self.alias)
+    }
+
+    fn matching_constructor(&self, name: &str) -> Option<&'static str> {
+        self.library
+            .constructors()
+            .iter()
+            .copied()
+            .find(|constructor| *constructor == name)
+    }
+}
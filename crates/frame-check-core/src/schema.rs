@@ -0,0 +1,182 @@
+//! Tracks the column names of DataFrame-producing expressions so later
+//! passes can validate references against them.
+
+use std::collections::HashMap;
+
+use ruff_python_ast::{Expr, ExprAttribute, ExprCall};
+use ruff_text_size::{Ranged, TextRange};
+
+use crate::resolver::Resolver;
+
+/// The inferred columns of a single DataFrame-valued variable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Schema {
+    pub columns: Vec<String>,
+    pub source: TextRange,
+}
+
+/// Maps variable names to the schema last inferred for them.
+pub type Schemas = HashMap<String, Schema>;
+
+/// Infers the schema produced by `expr`, if it is a call recognized as
+/// constructing or transforming a DataFrame, per `resolver`.
+///
+/// `schemas` is consulted when a chained call's receiver is a plain name
+/// rather than another call, e.g. `df2 = df.with_columns(...)`: the
+/// schema already tracked for `df` is looked up and used as the base,
+/// so transforms of a previously-assigned DataFrame are inferred too,
+/// not just a whole constructor-to-transform chain in one expression.
+pub fn infer_schema(expr: &Expr, resolver: &Resolver, schemas: &Schemas) -> Option<Schema> {
+    let call = expr.as_call_expr()?;
+
+    if let Some(constructor) = resolver.constructor_name(&call.func) {
+        return infer_constructor_schema(call, constructor);
+    }
+
+    // A chained call: `<base>.<attr>(...)`. Resolve the schema of the
+    // base expression, either by recursing (the base is itself a call)
+    // or by looking up an already-tracked variable, then apply the
+    // transform on top of it.
+    let attr = call.func.as_attribute_expr()?;
+    let base = match infer_schema(&attr.value, resolver, schemas) {
+        Some(base) => base,
+        None => {
+            let name = attr.value.as_name_expr()?;
+            schemas.get(name.id.as_str())?.clone()
+        }
+    };
+    apply_transform(base, call, attr, resolver)
+}
+
+fn infer_constructor_schema(call: &ExprCall, constructor: &str) -> Option<Schema> {
+    match constructor {
+        "DataFrame" => {
+            let dict = call.arguments.args.first()?.as_dict_expr()?;
+            let columns = dict
+                .iter()
+                .filter_map(|item| item.key.as_ref())
+                .filter_map(|key| key.as_string_literal_expr())
+                .map(|s| s.value.to_str().to_string())
+                .collect();
+            Some(Schema {
+                columns,
+                source: call.range(),
+            })
+        }
+        "read_csv" | "read_excel" => {
+            let columns = call
+                .arguments
+                .keywords
+                .iter()
+                .find(|kw| kw.arg.as_ref().is_some_and(|arg| arg.as_str() == "columns"))?
+                .value
+                .as_list_expr()?
+                .iter()
+                .filter_map(|elt| elt.as_string_literal_expr())
+                .map(|s| s.value.to_str().to_string())
+                .collect();
+            Some(Schema {
+                columns,
+                source: call.range(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// The column name a `with_columns`/`assign`-style argument introduces:
+/// `pl.col("a").alias("b")` and bare `pl.col("name")` (however `col` was
+/// imported) both name a column positionally, not just `new=...` keywords.
+fn added_column_name(expr: &Expr, resolver: &Resolver) -> Option<String> {
+    let call = expr.as_call_expr()?;
+    if let Some(attr) = call.func.as_attribute_expr() {
+        if attr.attr.as_str() == "alias" {
+            return call
+                .arguments
+                .args
+                .first()?
+                .as_string_literal_expr()
+                .map(|s| s.value.to_str().to_string());
+        }
+    }
+    if resolver.is_col_call(&call.func) {
+        return call
+            .arguments
+            .args
+            .first()?
+            .as_string_literal_expr()
+            .map(|s| s.value.to_str().to_string());
+    }
+    None
+}
+
+fn apply_transform(
+    mut base: Schema,
+    call: &ExprCall,
+    attr: &ExprAttribute,
+    resolver: &Resolver,
+) -> Option<Schema> {
+    let name = attr.attr.as_str();
+    let library = resolver.library;
+    base.source = call.range();
+
+    if library.adding_transforms().contains(&name) {
+        for arg in call.arguments.args.iter() {
+            if let Some(column) = added_column_name(arg, resolver) {
+                base.columns.push(column);
+            }
+        }
+        for kw in call.arguments.keywords.iter() {
+            if let Some(arg) = &kw.arg {
+                base.columns.push(arg.as_str().to_string());
+            }
+        }
+        return Some(base);
+    }
+
+    if library.rename_transforms().contains(&name) {
+        if let Some(mapping) = call.arguments.args.first().and_then(Expr::as_dict_expr) {
+            for item in mapping.iter() {
+                let Some(old) = item.key.as_ref().and_then(Expr::as_string_literal_expr) else {
+                    continue;
+                };
+                let Some(new) = item.value.as_string_literal_expr() else {
+                    continue;
+                };
+                if let Some(column) = base
+                    .columns
+                    .iter_mut()
+                    .find(|c| c.as_str() == old.value.to_str())
+                {
+                    *column = new.value.to_str().to_string();
+                }
+            }
+        }
+        for kw in call.arguments.keywords.iter() {
+            let (Some(old), Some(new)) = (kw.arg.as_ref(), kw.value.as_string_literal_expr())
+            else {
+                continue;
+            };
+            if let Some(column) = base.columns.iter_mut().find(|c| c.as_str() == old.as_str()) {
+                *column = new.value.to_str().to_string();
+            }
+        }
+        return Some(base);
+    }
+
+    if library.dropping_transforms().contains(&name) {
+        let dropped: Vec<&str> = call
+            .arguments
+            .args
+            .iter()
+            .filter_map(Expr::as_string_literal_expr)
+            .map(|s| s.value.to_str())
+            .collect();
+        base.columns.retain(|c| !dropped.contains(&c.as_str()));
+        return Some(base);
+    }
+
+    // Unknown transform on a tracked DataFrame: keep the schema as-is
+    // rather than dropping it, since most methods don't change columns.
+    Some(base)
+}
@@ -0,0 +1,148 @@
+//! `frame-check-lsp`: the language server the Zed extension launches.
+//!
+//! Runs the standard `initialize` handshake, then tracks open buffers and
+//! republishes diagnostics from [`frame_check_core`] on every
+//! `textDocument/didOpen`/`didChange`.
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use frame_check_core::{
+    Context, Diagnostic as CoreDiagnostic, Library,
+    db::{Db, FileId},
+    diagnostic::Severity,
+};
+use lsp_server::{Connection, Message, Notification};
+use lsp_types::{
+    Diagnostic, DiagnosticSeverity, Position, PublishDiagnosticsParams, Range,
+    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+    notification::{
+        DidChangeTextDocument, DidOpenTextDocument, Notification as _, PublishDiagnostics,
+    },
+};
+
+fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        ..Default::default()
+    };
+    let initialize_params = connection.initialize(serde_json::to_value(capabilities)?)?;
+    let library = library_from_initialize_params(&initialize_params);
+
+    run(&connection, library)?;
+    io_threads.join()?;
+    Ok(())
+}
+
+/// Reads the `library` field out of the client's `initializationOptions`
+/// (e.g. `{"library": "pandas"}`), falling back to polars if the client
+/// didn't send one or named a library we don't recognize.
+fn library_from_initialize_params(initialize_params: &serde_json::Value) -> Library {
+    initialize_params
+        .get("initializationOptions")
+        .and_then(|options| options.get("library"))
+        .and_then(serde_json::Value::as_str)
+        .and_then(Library::from_name)
+        .unwrap_or(Library::Polars)
+}
+
+fn run(connection: &Connection, library: Library) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let db = Db::new();
+    let mut open_files: HashMap<Url, FileId> = HashMap::new();
+
+    for message in &connection.receiver {
+        match message {
+            Message::Notification(notification) => {
+                handle_notification(connection, &db, library, &mut open_files, notification)?;
+            }
+            Message::Request(request) if connection.handle_shutdown(&request)? => break,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn handle_notification(
+    connection: &Connection,
+    db: &Db,
+    library: Library,
+    open_files: &mut HashMap<Url, FileId>,
+    notification: Notification,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    match notification.method.as_str() {
+        DidOpenTextDocument::METHOD => {
+            let params: lsp_types::DidOpenTextDocumentParams =
+                serde_json::from_value(notification.params)?;
+            let file = db.add_file(params.text_document.text);
+            open_files.insert(params.text_document.uri.clone(), file);
+            publish_diagnostics(connection, db, library, &params.text_document.uri, file)?;
+        }
+        DidChangeTextDocument::METHOD => {
+            let params: lsp_types::DidChangeTextDocumentParams =
+                serde_json::from_value(notification.params)?;
+            if let (Some(file), Some(change)) = (
+                open_files.get(&params.text_document.uri).copied(),
+                params.content_changes.into_iter().next_back(),
+            ) {
+                db.set_source_text(file, change.text);
+                publish_diagnostics(connection, db, library, &params.text_document.uri, file)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn publish_diagnostics(
+    connection: &Connection,
+    db: &Db,
+    library: Library,
+    uri: &Url,
+    file: FileId,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let text = db.source_text(file);
+    let module = db.parsed_module(file);
+    let line_index = db.line_index(file);
+
+    let mut ctx = Context::new(library);
+    let diagnostics: Vec<Diagnostic> = ctx
+        .analyze(&module)
+        .iter()
+        .map(|diagnostic| to_lsp_diagnostic(diagnostic, &text, &line_index))
+        .collect();
+
+    let params = PublishDiagnosticsParams {
+        uri: uri.clone(),
+        diagnostics,
+        version: None,
+    };
+    connection.sender.send(Message::Notification(Notification {
+        method: PublishDiagnostics::METHOD.to_string(),
+        params: serde_json::to_value(params)?,
+    }))?;
+    Ok(())
+}
+
+fn to_lsp_diagnostic(
+    diagnostic: &CoreDiagnostic,
+    text: &str,
+    line_index: &frame_check_core::line_index::LineIndex,
+) -> Diagnostic {
+    let start = line_index.line_column(diagnostic.range.start(), text);
+    let end = line_index.line_column(diagnostic.range.end(), text);
+    Diagnostic {
+        range: Range {
+            start: Position::new(start.line, start.column),
+            end: Position::new(end.line, end.column),
+        },
+        severity: Some(match diagnostic.severity {
+            Severity::Error => DiagnosticSeverity::ERROR,
+            Severity::Warning => DiagnosticSeverity::WARNING,
+        }),
+        source: Some("frame-check".to_string()),
+        message: diagnostic.message.clone(),
+        ..Default::default()
+    }
+}